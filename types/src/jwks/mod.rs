@@ -0,0 +1,89 @@
+// Copyright © Aptos Foundation
+
+pub mod ec;
+pub mod okp;
+pub mod rsa;
+
+use anyhow::{bail, Result};
+use ec::EC_JWK;
+use okp::OKP_JWK;
+use rsa::RSA_JWK;
+use serde::{Deserialize, Serialize};
+
+/// A JSON Web Key (https://datatracker.ietf.org/doc/html/rfc7517), covering the key types whose
+/// `alg` zkID JWT verification supports: `RSA` (`RS256`), `EC` (`ES256`), and `OKP` (`EdDSA`).
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Hash, Serialize)]
+pub enum JWK {
+    RSA(RSA_JWK),
+    EC(EC_JWK),
+    OKP(OKP_JWK),
+}
+
+impl JWK {
+    /// The `alg` value(s) this JWK's key type is allowed to sign with.
+    fn supports_alg(&self, alg: &str) -> bool {
+        match self {
+            JWK::RSA(_) => alg == "RS256",
+            JWK::EC(_) => alg == "ES256",
+            JWK::OKP(_) => alg == "EdDSA",
+        }
+    }
+
+    /// Verifies `jwt_token` (a `header.payload.signature` compact-serialized JWT) was signed
+    /// using `alg`, rejecting `alg: "none"` and any mismatch between `alg` and this JWK's key
+    /// type.
+    pub fn verify_signature(&self, alg: &str, jwt_token: &str) -> Result<()> {
+        if alg.eq_ignore_ascii_case("none") {
+            bail!("alg 'none' is not allowed");
+        }
+        if !self.supports_alg(alg) {
+            bail!(
+                "JWT header alg \"{}\" does not match the selected JWK's key type",
+                alg
+            );
+        }
+
+        match self {
+            JWK::RSA(rsa_jwk) => rsa_jwk.verify_signature(jwt_token),
+            JWK::EC(ec_jwk) => ec_jwk.verify_signature(jwt_token),
+            JWK::OKP(okp_jwk) => okp_jwk.verify_signature(jwt_token),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rejects_alg_none() {
+        let jwk = JWK::EC(EC_JWK {
+            kid: "test-kid".to_string(),
+            kty: "EC".to_string(),
+            alg: "ES256".to_string(),
+            crv: "P-256".to_string(),
+            x: "".to_string(),
+            y: "".to_string(),
+        });
+
+        jwk.verify_signature("none", "header.payload.sig").unwrap_err();
+    }
+
+    #[test]
+    fn rejects_alg_key_type_mismatch() {
+        let jwk = JWK::OKP(OKP_JWK {
+            kid: "test-kid".to_string(),
+            kty: "OKP".to_string(),
+            alg: "EdDSA".to_string(),
+            crv: "Ed25519".to_string(),
+            x: "".to_string(),
+        });
+
+        // Neither an EC_JWK's alg (ES256) nor an RSA_JWK's alg (RS256) should be verifiable
+        // against an OKP_JWK.
+        jwk.verify_signature("ES256", "header.payload.sig")
+            .unwrap_err();
+        jwk.verify_signature("RS256", "header.payload.sig")
+            .unwrap_err();
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright © Aptos Foundation
+
+use anyhow::{ensure, Result};
+use ed25519_dalek::{PublicKey, Signature, Verifier};
+use serde::{Deserialize, Serialize};
+
+/// Reflects a JSON Web Key (https://datatracker.ietf.org/doc/html/rfc7517) parsed from an OIDC
+/// provider's JWK set, for `kty: "OKP"`; i.e., an Ed25519 public key used to verify JWT
+/// signatures signed with `alg: "EdDSA"`.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Hash, Serialize)]
+pub struct OKP_JWK {
+    pub kid: String,
+    pub kty: String,
+    pub alg: String,
+    pub crv: String,
+    pub x: String,
+}
+
+impl OKP_JWK {
+    /// Verifies the EdDSA signature over a `header.payload.signature` compact-serialized JWT.
+    pub fn verify_signature(&self, jwt_token: &str) -> Result<()> {
+        ensure!(
+            self.crv == "Ed25519",
+            "OKP_JWK curve \"{}\" is not supported; only Ed25519 is",
+            self.crv
+        );
+
+        let parts: Vec<&str> = jwt_token.rsplitn(2, '.').collect();
+        let [sig_b64, signing_input] = <[&str; 2]>::try_from(parts).map_err(|_| {
+            anyhow::anyhow!("JWT token must have the form header.payload.signature")
+        })?;
+
+        let x = base64::decode_config(&self.x, base64::URL_SAFE_NO_PAD)?;
+        let public_key = PublicKey::from_bytes(&x)?;
+
+        let sig_bytes = base64::decode_config(sig_b64, base64::URL_SAFE_NO_PAD)?;
+        let signature = Signature::from_bytes(&sig_bytes)?;
+
+        public_key.verify(signing_input.as_bytes(), &signature)?;
+        Ok(())
+    }
+}
+
+impl TryFrom<&[u8]> for OKP_JWK {
+    type Error = anyhow::Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes).map_err(|e| anyhow::anyhow!(e))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ed25519_dalek::{Keypair, Signer};
+
+    fn jwk_and_token(keypair: &Keypair, signing_input: &str) -> (OKP_JWK, String) {
+        let jwk = OKP_JWK {
+            kid: "test-kid".to_string(),
+            kty: "OKP".to_string(),
+            alg: "EdDSA".to_string(),
+            crv: "Ed25519".to_string(),
+            x: base64::encode_config(keypair.public.as_bytes(), base64::URL_SAFE_NO_PAD),
+        };
+
+        let signature = keypair.sign(signing_input.as_bytes());
+        let jwt_token = format!(
+            "{}.{}",
+            signing_input,
+            base64::encode_config(signature.to_bytes(), base64::URL_SAFE_NO_PAD)
+        );
+
+        (jwk, jwt_token)
+    }
+
+    #[test]
+    fn verifies_a_valid_eddsa_signature() {
+        let keypair = Keypair::generate(&mut rand::rngs::OsRng);
+        let (jwk, jwt_token) = jwk_and_token(&keypair, "header.payload");
+
+        jwk.verify_signature(&jwt_token).unwrap();
+    }
+
+    #[test]
+    fn rejects_a_tampered_payload() {
+        let keypair = Keypair::generate(&mut rand::rngs::OsRng);
+        let (jwk, jwt_token) = jwk_and_token(&keypair, "header.payload");
+        let tampered_token = jwt_token.replacen("header.payload", "header.tampered", 1);
+
+        jwk.verify_signature(&tampered_token).unwrap_err();
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright © Aptos Foundation
+
+use anyhow::Result;
+use rsa::{pkcs1v15::Pkcs1v15Sign, BigUint, RsaPublicKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Reflects a JSON Web Key (https://datatracker.ietf.org/doc/html/rfc7517) parsed from an OIDC
+/// provider's JWK set, for `kty: "RSA"`; i.e., an RSA public key used to verify JWT signatures
+/// signed with `alg: "RS256"`.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Hash, Serialize)]
+pub struct RSA_JWK {
+    pub kid: String,
+    pub kty: String,
+    pub alg: String,
+    pub e: String,
+    pub n: String,
+}
+
+impl RSA_JWK {
+    /// Verifies the RS256 signature over a `header.payload.signature` compact-serialized JWT.
+    pub fn verify_signature(&self, jwt_token: &str) -> Result<()> {
+        let parts: Vec<&str> = jwt_token.rsplitn(2, '.').collect();
+        let [sig_b64, signing_input] = <[&str; 2]>::try_from(parts).map_err(|_| {
+            anyhow::anyhow!("JWT token must have the form header.payload.signature")
+        })?;
+
+        let n = BigUint::from_bytes_be(&base64::decode_config(
+            &self.n,
+            base64::URL_SAFE_NO_PAD,
+        )?);
+        let e = BigUint::from_bytes_be(&base64::decode_config(
+            &self.e,
+            base64::URL_SAFE_NO_PAD,
+        )?);
+        let public_key = RsaPublicKey::new(n, e)?;
+
+        let sig = base64::decode_config(sig_b64, base64::URL_SAFE_NO_PAD)?;
+        let digest = Sha256::digest(signing_input.as_bytes());
+
+        public_key.verify(Pkcs1v15Sign::new::<Sha256>(), &digest, &sig)?;
+        Ok(())
+    }
+}
+
+impl TryFrom<&[u8]> for RSA_JWK {
+    type Error = anyhow::Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes).map_err(|e| anyhow::anyhow!(e))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rsa::{RsaPrivateKey, PublicKeyParts};
+
+    fn jwk_and_token(private_key: &RsaPrivateKey, signing_input: &str) -> (RSA_JWK, String) {
+        let jwk = RSA_JWK {
+            kid: "test-kid".to_string(),
+            kty: "RSA".to_string(),
+            alg: "RS256".to_string(),
+            e: base64::encode_config(private_key.e().to_bytes_be(), base64::URL_SAFE_NO_PAD),
+            n: base64::encode_config(private_key.n().to_bytes_be(), base64::URL_SAFE_NO_PAD),
+        };
+
+        let digest = Sha256::digest(signing_input.as_bytes());
+        let signature = private_key
+            .sign(Pkcs1v15Sign::new::<Sha256>(), &digest)
+            .unwrap();
+        let jwt_token = format!(
+            "{}.{}",
+            signing_input,
+            base64::encode_config(signature, base64::URL_SAFE_NO_PAD)
+        );
+
+        (jwk, jwt_token)
+    }
+
+    #[test]
+    fn verifies_a_valid_rs256_signature() {
+        let private_key =
+            RsaPrivateKey::new(&mut rand::thread_rng(), 2048).expect("key generation failed");
+        let (jwk, jwt_token) = jwk_and_token(&private_key, "header.payload");
+
+        jwk.verify_signature(&jwt_token).unwrap();
+    }
+
+    #[test]
+    fn rejects_a_tampered_payload() {
+        let private_key =
+            RsaPrivateKey::new(&mut rand::thread_rng(), 2048).expect("key generation failed");
+        let (jwk, jwt_token) = jwk_and_token(&private_key, "header.payload");
+        let tampered_token = jwt_token.replacen("header.payload", "header.tampered", 1);
+
+        jwk.verify_signature(&tampered_token).unwrap_err();
+    }
+}
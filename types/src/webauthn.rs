@@ -0,0 +1,161 @@
+// Copyright © Aptos Foundation
+
+use crate::transaction::authenticator::EphemeralPublicKey;
+use anyhow::{ensure, Result};
+use aptos_crypto::ValidCryptoMaterial;
+use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Bit 0 (user presence) of the flags byte in `authenticator_data`
+/// (https://www.w3.org/TR/webauthn-2/#sctn-authenticator-data).
+const USER_PRESENT_FLAG: u8 = 0x01;
+/// The offset of the flags byte in `authenticator_data`: a 32-byte `rpIdHash` precedes it.
+const FLAGS_OFFSET: usize = 32;
+
+/// The subset of a CTAP2/WebAuthn `clientDataJSON` (https://www.w3.org/TR/webauthn-2/#dictdef-collectedclientdata)
+/// that assertion verification needs.
+#[derive(Debug, Deserialize, Serialize)]
+struct ClientData {
+    #[serde(rename = "type")]
+    type_: String,
+    challenge: String,
+}
+
+/// A WebAuthn authenticator assertion (https://www.w3.org/TR/webauthn-2/#sctn-verifying-assertion),
+/// used as an `EphemeralSignature` alternative so the zkID ephemeral key can live in a platform
+/// authenticator (passkey) rather than in software held by the wallet.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Hash, Serialize)]
+pub struct WebAuthnAssertion {
+    /// Authenticator-produced data (https://www.w3.org/TR/webauthn-2/#authenticator-data):
+    /// `rpIdHash (32 bytes) || flags (1 byte) || signCount (4 bytes) || ...`.
+    pub authenticator_data: Vec<u8>,
+    /// The raw, UTF-8-encoded `clientDataJSON` returned by the authenticator.
+    pub client_data_json: Vec<u8>,
+    /// The raw (r, s) P-256 ECDSA signature over `authenticator_data || SHA-256(client_data_json)`.
+    pub signature: Vec<u8>,
+}
+
+impl WebAuthnAssertion {
+    /// Verifies this assertion against `ephemeral_pubkey`, checking that:
+    ///  1. `client_data_json` has `type: "webauthn.get"` and its `challenge` equals
+    ///     `expected_challenge` (the transaction signing message);
+    ///  2. the P-256 signature over `authenticator_data || SHA-256(client_data_json)` is valid;
+    ///  3. the user-presence flag is set in `authenticator_data`.
+    pub fn verify(
+        &self,
+        ephemeral_pubkey: &EphemeralPublicKey,
+        expected_challenge: &[u8],
+    ) -> Result<()> {
+        let client_data: ClientData = serde_json::from_slice(&self.client_data_json)?;
+        ensure!(
+            client_data.type_ == "webauthn.get",
+            "clientDataJSON 'type' must be \"webauthn.get\", was \"{}\"",
+            client_data.type_
+        );
+
+        let challenge = base64::decode_config(&client_data.challenge, base64::URL_SAFE_NO_PAD)?;
+        ensure!(
+            challenge == expected_challenge,
+            "clientDataJSON 'challenge' did not match the expected transaction signing message"
+        );
+
+        ensure!(
+            self.authenticator_data.len() > FLAGS_OFFSET,
+            "authenticator_data is too short to contain a flags byte"
+        );
+        let flags = self.authenticator_data[FLAGS_OFFSET];
+        ensure!(
+            flags & USER_PRESENT_FLAG != 0,
+            "authenticator_data does not have the user-present flag set"
+        );
+
+        let client_data_hash = Sha256::digest(&self.client_data_json);
+        let mut signed_data = self.authenticator_data.clone();
+        signed_data.extend_from_slice(&client_data_hash);
+
+        let verifying_key = VerifyingKey::from_sec1_bytes(&ephemeral_pubkey.to_bytes())?;
+        let signature = Signature::from_slice(&self.signature)?;
+        verifying_key.verify(&signed_data, &signature)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aptos_crypto::secp256r1_ecdsa;
+    use p256::ecdsa::{signature::Signer, SigningKey};
+
+    fn sample_assertion(
+        signing_key: &SigningKey,
+        challenge: &[u8],
+    ) -> (EphemeralPublicKey, WebAuthnAssertion) {
+        let encoded_point = signing_key.verifying_key().to_encoded_point(false);
+        let ephemeral_pubkey = EphemeralPublicKey::Secp256r1Ecdsa {
+            public_key: secp256r1_ecdsa::PublicKey::try_from(encoded_point.as_bytes())
+                .expect("valid P-256 public key"),
+        };
+
+        // A minimal `rpIdHash (32 bytes) || flags (1 byte, user-present) || signCount (4 bytes)`.
+        let mut authenticator_data = vec![0u8; 37];
+        authenticator_data[FLAGS_OFFSET] = USER_PRESENT_FLAG;
+
+        let client_data_json = serde_json::to_vec(&ClientData {
+            type_: "webauthn.get".to_string(),
+            challenge: base64::encode_config(challenge, base64::URL_SAFE_NO_PAD),
+        })
+        .unwrap();
+
+        let client_data_hash = Sha256::digest(&client_data_json);
+        let mut signed_data = authenticator_data.clone();
+        signed_data.extend_from_slice(&client_data_hash);
+        let signature: Signature = signing_key.sign(&signed_data);
+
+        (ephemeral_pubkey, WebAuthnAssertion {
+            authenticator_data,
+            client_data_json,
+            signature: signature.to_bytes().to_vec(),
+        })
+    }
+
+    #[test]
+    fn verifies_a_valid_assertion() {
+        let signing_key = SigningKey::random(&mut rand::thread_rng());
+        let challenge = b"transaction-signing-message";
+        let (ephemeral_pubkey, assertion) = sample_assertion(&signing_key, challenge);
+
+        assertion.verify(&ephemeral_pubkey, challenge).unwrap();
+    }
+
+    #[test]
+    fn rejects_a_mismatched_challenge() {
+        let signing_key = SigningKey::random(&mut rand::thread_rng());
+        let (ephemeral_pubkey, assertion) = sample_assertion(&signing_key, b"expected-message");
+
+        assertion
+            .verify(&ephemeral_pubkey, b"different-message")
+            .unwrap_err();
+    }
+
+    #[test]
+    fn rejects_a_missing_user_presence_flag() {
+        let signing_key = SigningKey::random(&mut rand::thread_rng());
+        let challenge = b"transaction-signing-message";
+        let (ephemeral_pubkey, mut assertion) = sample_assertion(&signing_key, challenge);
+        assertion.authenticator_data[FLAGS_OFFSET] = 0x00;
+
+        assertion.verify(&ephemeral_pubkey, challenge).unwrap_err();
+    }
+
+    #[test]
+    fn rejects_a_tampered_signature() {
+        let signing_key = SigningKey::random(&mut rand::thread_rng());
+        let challenge = b"transaction-signing-message";
+        let (ephemeral_pubkey, mut assertion) = sample_assertion(&signing_key, challenge);
+        assertion.signature[0] ^= 0xff;
+
+        assertion.verify(&ephemeral_pubkey, challenge).unwrap_err();
+    }
+}
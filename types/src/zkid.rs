@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 
 use crate::{
-    jwks::rsa::RSA_JWK,
+    jwks::{rsa::RSA_JWK, JWK},
     on_chain_config::CurrentTimeMicroseconds,
     transaction::{
         authenticator::{
@@ -9,11 +9,13 @@ use crate::{
         },
         SignedTransaction,
     },
+    webauthn::WebAuthnAssertion,
 };
-use anyhow::{anyhow, ensure, Context, Ok, Result};
+use anyhow::{anyhow, bail, ensure, Context, Ok, Result};
 use aptos_crypto::{poseidon_bn254, CryptoMaterialError, ValidCryptoMaterial};
 use ark_bn254;
-use ark_serialize::CanonicalSerialize;
+use ark_groth16::PreparedVerifyingKey;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use base64::{URL_SAFE, URL_SAFE_NO_PAD};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -38,6 +40,7 @@ pub const MAX_AUD_VAL_BYTES: usize = 248;
 pub const MAX_UID_KEY_BYTES: usize = 248;
 pub const MAX_UID_VAL_BYTES: usize = 248;
 pub const MAX_JWT_HEADER_BYTES: usize = 248;
+pub const MAX_RSA_MODULUS_BYTES: usize = 256;
 
 pub const MAX_ZK_PUBLIC_KEY_BYTES: usize = MAX_ISS_BYTES + MAX_EPK_BYTES;
 
@@ -51,6 +54,23 @@ pub const MAX_ZK_ID_AUTHENTICATORS_ALLOWED: usize = 10;
 // How far in the future from the JWT issued at time the EPK expiry can be set.
 pub const MAX_EXPIRY_HORIZON_SECS: u64 = 1728000000; // 20000 days TODO(zkid): finalize this value
 
+/// How far into the future a JWT's `iat` claim is allowed to be, to tolerate clock skew between
+/// the OIDC provider and this node, while still rejecting a JWT "issued" implausibly far ahead.
+pub const MAX_IAT_CLOCK_SKEW_SECS: u64 = 300; // 5 minutes
+
+/// The highest `ZkIdSignature::version` this node understands. Bumped whenever the circuit's
+/// public inputs, the nonce-commitment construction, or the claim set changes in a way that isn't
+/// backwards compatible; signatures above this version are rejected rather than misinterpreted.
+pub const MAX_SUPPORTED_VERSION: u8 = 0;
+
+/// Returns the `MAX_EPK_BYTES` field-layout bound that applies to a given `ZkIdSignature::version`.
+fn max_epk_bytes_for_version(version: u8) -> Result<usize> {
+    match version {
+        0 => Ok(MAX_EPK_BYTES),
+        v => Err(anyhow!("Unsupported zkID signature version {}", v)),
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct JwkId {
     /// The OIDC provider associated with this JWK
@@ -79,11 +99,17 @@ impl OpenIdSig {
     ///  2. Check that the iss claim in the ZkIdPublicKey matches the one in the jwt_payload
     ///  3. Check that the identity commitment in the ZkIdPublicKey matches the one constructed from the jwt_payload
     ///  4. Check that the nonce constructed from the ephemeral public key, blinder, and exp_timestamp_secs matches the one in the jwt_payload
+    ///  5. Check the standard OIDC time-based claims (`exp`, `nbf`, clock skew on `iat`) against `current_time`
+    ///  6. Resolve the intended `aud` (honoring `azp` when `aud` is an array) and check it against
+    ///     the committed IDC, either directly or via one of the governance-approved `recovery_auds`
     pub fn verify_jwt_claims(
         &self,
+        version: u8,
         exp_timestamp_secs: u64,
         epk: &EphemeralPublicKey,
         pk: &ZkIdPublicKey,
+        current_time: &CurrentTimeMicroseconds,
+        recovery_auds: &[String],
     ) -> Result<()> {
         let jwt_payload_json = base64url_to_str(&self.jwt_payload)?;
         let claims: Claims = serde_json::from_str(&jwt_payload_json)?;
@@ -98,6 +124,26 @@ impl OpenIdSig {
             "The ephemeral public key's expiration date is too far into the future"
         );
 
+        let block_time = UNIX_EPOCH + Duration::from_micros(current_time.microseconds);
+
+        ensure!(
+            block_time < seconds_from_epoch(claims.oidc_claims.exp),
+            "'exp' claim indicates an expired JWT"
+        );
+
+        if let Some(nbf) = claims.oidc_claims.nbf {
+            ensure!(
+                block_time >= seconds_from_epoch(nbf),
+                "JWT is not yet valid per its 'nbf' claim"
+            );
+        }
+
+        ensure!(
+            seconds_from_epoch(claims.oidc_claims.iat)
+                < block_time + Duration::from_secs(MAX_IAT_CLOCK_SKEW_SECS),
+            "'iat' claim is implausibly far in the future"
+        );
+
         ensure!(
             claims.oidc_claims.iss.eq(&pk.iss),
             "'iss' claim was supposed to match \"{}\"",
@@ -111,19 +157,22 @@ impl OpenIdSig {
         );
         let uid_val = claims.get_uid_val(&self.uid_key)?;
 
-        ensure!(
-            IdCommitment::new_from_preimage(
-                &claims.oidc_claims.aud,
-                &self.uid_key,
-                &uid_val,
-                &self.pepper
-            )?
-            .eq(&pk.idc),
-            "Address IDC verification failed"
-        );
+        let aud = claims
+            .oidc_claims
+            .aud
+            .intended_aud(claims.oidc_claims.azp.as_deref())?;
+
+        match_aud_against_idc(
+            &aud,
+            &self.uid_key,
+            &uid_val,
+            &self.pepper,
+            &pk.idc,
+            recovery_auds,
+        )?;
 
         ensure!(
-            self.reconstruct_oauth_nonce(exp_timestamp_secs, epk)?
+            self.reconstruct_oauth_nonce(version, exp_timestamp_secs, epk)?
                 .eq(&claims.oidc_claims.nonce),
             "'nonce' claim did not contain the expected EPK and expiration date commitment"
         );
@@ -131,22 +180,26 @@ impl OpenIdSig {
         Ok(())
     }
 
-    pub fn verify_jwt_signature(&self, rsa_jwk: RSA_JWK, jwt_header: &String) -> Result<()> {
+    /// Verifies the JWT's signature using the JWK selected for the `iss`/`kid`, dispatching on
+    /// the `alg` in the JWT header so that non-RSA OIDC providers (e.g., ones signing with
+    /// `ES256` or `EdDSA`) are supported, not just `RS256`.
+    pub fn verify_jwt_signature(&self, jwk: JWK, jwt_header: &String) -> Result<()> {
+        let header: JWTHeader = serde_json::from_str(&base64url_to_str(jwt_header)?)?;
         let jwt_payload = &self.jwt_payload;
         let jwt_sig = &self.jwt_sig;
         let jwt_token = format!("{}.{}.{}", jwt_header, jwt_payload, jwt_sig);
-        rsa_jwk.verify_signature(&jwt_token)?;
-        Ok(())
+        jwk.verify_signature(&header.alg, &jwt_token)
     }
 
     pub fn reconstruct_oauth_nonce(
         &self,
+        version: u8,
         exp_timestamp_secs: u64,
         epk: &EphemeralPublicKey,
     ) -> Result<String> {
         let mut frs = poseidon_bn254::pad_and_pack_bytes_to_scalars_with_len(
             epk.to_bytes().as_slice(),
-            MAX_EPK_BYTES,
+            max_epk_bytes_for_version(version)?,
         )?;
 
         frs.push(ark_bn254::Fr::from(exp_timestamp_secs));
@@ -170,14 +223,58 @@ impl TryFrom<&[u8]> for OpenIdSig {
     }
 }
 
+/// The standard `aud` claim (https://datatracker.ietf.org/doc/html/rfc7519#section-4.1.3) is
+/// usually a single OAuth client ID, but the spec also allows an array of intended audiences.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AudClaim {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl AudClaim {
+    /// Resolves the single intended audience per the OIDC Core spec
+    /// (https://openid.net/specs/openid-connect-core-1_0.html#IDToken): when `aud` is an array,
+    /// `azp` must be present and must be one of the `aud` values, and is then the intended
+    /// audience; when `aud` is a single string, it is the intended audience (and, if present,
+    /// `azp` must agree with it).
+    fn intended_aud(&self, azp: Option<&str>) -> Result<String> {
+        match self {
+            AudClaim::Single(aud) => {
+                if let Some(azp) = azp {
+                    ensure!(
+                        azp == aud,
+                        "'azp' claim \"{}\" did not match the single-valued 'aud' claim \"{}\"",
+                        azp,
+                        aud
+                    );
+                }
+                Ok(aud.clone())
+            },
+            AudClaim::Multiple(auds) => {
+                let azp = azp.context("'azp' claim is required when 'aud' is an array")?;
+                ensure!(
+                    auds.iter().any(|aud| aud == azp),
+                    "'azp' claim \"{}\" is not one of the 'aud' claim's values",
+                    azp
+                );
+                Ok(azp.to_string())
+            },
+        }
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OidcClaims {
     iss: String,
-    aud: String,
+    aud: AudClaim,
     sub: String,
     nonce: String,
     iat: u64,
+    exp: u64,
+    nbf: Option<u64>,
+    azp: Option<String>,
     email: Option<String>,
     email_verified: Option<Value>,
 }
@@ -242,6 +339,95 @@ impl TryFrom<&[u8]> for Groth16Zkp {
     }
 }
 
+impl Groth16Zkp {
+    /// Checks the Groth16 pairing equation `e(A,B) == e(alpha,beta) \* e(vk_x,gamma) \* e(C,delta)`
+    /// over BN254, where `vk_x = IC[0] + public_inputs_hash \* IC[1]`, against the single public
+    /// input produced by [`compute_public_inputs_hash`].
+    pub fn verify_proof(
+        &self,
+        public_inputs_hash: ark_bn254::Fr,
+        pvk: &PreparedVerifyingKey<ark_bn254::Bn254>,
+    ) -> Result<()> {
+        let proof = ark_groth16::Proof::<ark_bn254::Bn254> {
+            a: parse_g1(&self.a)?,
+            b: parse_g2(&self.b)?,
+            c: parse_g1(&self.c)?,
+        };
+
+        let verified =
+            ark_groth16::Groth16::<ark_bn254::Bn254>::verify_proof(pvk, &proof, &[
+                public_inputs_hash,
+            ])?;
+        ensure!(verified, "Groth16 proof verification failed");
+        Ok(())
+    }
+}
+
+/// Computes the single Poseidon-BN254 public input that the zkID circuit's Groth16 proof attests
+/// to: a hash of the OIDC `iss`, the `IdCommitment`, the packed `EphemeralPublicKey`, the EPK's
+/// expiration time, and the RSA JWK modulus used to originally sign the JWT.
+pub fn compute_public_inputs_hash(
+    version: u8,
+    pk: &ZkIdPublicKey,
+    epk: &EphemeralPublicKey,
+    rsa_jwk: &RSA_JWK,
+    exp_timestamp_secs: u64,
+) -> Result<ark_bn254::Fr> {
+    let mut frs = vec![poseidon_bn254::pad_and_hash_string(&pk.iss, MAX_ISS_BYTES)?];
+
+    frs.push(ark_bn254::Fr::deserialize_uncompressed(&pk.idc.0[..])?);
+
+    frs.extend(poseidon_bn254::pad_and_pack_bytes_to_scalars_with_len(
+        epk.to_bytes().as_slice(),
+        max_epk_bytes_for_version(version)?,
+    )?);
+
+    frs.push(ark_bn254::Fr::from(exp_timestamp_secs));
+
+    let modulus = base64::decode_config(&rsa_jwk.n, URL_SAFE_NO_PAD)?;
+    frs.extend(poseidon_bn254::pad_and_pack_bytes_to_scalars_with_len(
+        &modulus,
+        MAX_RSA_MODULUS_BYTES,
+    )?);
+
+    poseidon_bn254::hash_scalars(frs)
+}
+
+fn parse_fq(s: &str) -> Result<ark_bn254::Fq> {
+    s.parse::<ark_bn254::Fq>()
+        .map_err(|_| anyhow!("could not parse \"{}\" as a BN254 base field element", s))
+}
+
+fn parse_g1(g1: &G1) -> Result<ark_bn254::G1Affine> {
+    ensure!(g1.len() == 2, "a G1 point must have exactly 2 limbs");
+    // `Affine::new` panics on an off-curve point instead of returning a `Result`, and these limbs
+    // come verbatim from a submitted transaction, so build the point with `new_unchecked` and
+    // check membership ourselves.
+    let point = ark_bn254::G1Affine::new_unchecked(parse_fq(&g1[0])?, parse_fq(&g1[1])?);
+    ensure!(point.is_on_curve(), "G1 point is not on the BN254 curve");
+    Ok(point)
+}
+
+fn parse_g2(g2: &G2) -> Result<ark_bn254::G2Affine> {
+    ensure!(g2.len() == 2, "a G2 point must have exactly 2 limbs");
+    ensure!(
+        g2[0].len() == 2 && g2[1].len() == 2,
+        "a G2 point's limbs must each be a 2-element Fq2"
+    );
+    // snarkjs encodes Fq2 coordinates as [c0, c1] in its `c1*u + c0` basis.
+    let x = ark_bn254::Fq2::new(parse_fq(&g2[0][0])?, parse_fq(&g2[0][1])?);
+    let y = ark_bn254::Fq2::new(parse_fq(&g2[1][0])?, parse_fq(&g2[1][1])?);
+    // As with G1, avoid the panicking `Affine::new` on attacker-supplied limbs. G2's cofactor is
+    // not 1, so an on-curve point must also be checked to be in the correct prime-order subgroup.
+    let point = ark_bn254::G2Affine::new_unchecked(x, y);
+    ensure!(point.is_on_curve(), "G2 point is not on the BN254 curve");
+    ensure!(
+        point.is_in_correct_subgroup_assuming_on_curve(),
+        "G2 point is not in the correct subgroup"
+    );
+    Ok(point)
+}
+
 /// Allows us to support direct verification of OpenID signatures, in the rare case that we would
 /// need to turn off ZK proofs due to a bug in the circuit.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Hash, Serialize)]
@@ -250,8 +436,43 @@ pub enum ZkpOrOpenIdSig {
     OpenIdSig(OpenIdSig),
 }
 
+/// How the ephemeral keypair signed over the transaction: either a raw signature from a
+/// software-held ephemeral keypair, or a WebAuthn assertion from a platform authenticator
+/// (passkey), so the ephemeral key can be hardware-backed instead.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Hash, Serialize)]
+pub enum ZkIdEphemeralSignature {
+    EphemeralSignature(EphemeralSignature),
+    WebAuthn(WebAuthnAssertion),
+}
+
+impl ZkIdEphemeralSignature {
+    /// Verifies that this signature was produced by `ephemeral_pubkey` over `signing_message`
+    /// (the transaction's signing message).
+    pub fn verify(
+        &self,
+        ephemeral_pubkey: &EphemeralPublicKey,
+        signing_message: &[u8],
+    ) -> Result<()> {
+        match self {
+            ZkIdEphemeralSignature::EphemeralSignature(sig) => {
+                sig.verify_arbitrary_msg(signing_message, ephemeral_pubkey)
+            },
+            ZkIdEphemeralSignature::WebAuthn(assertion) => {
+                assertion.verify(ephemeral_pubkey, signing_message)
+            },
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Hash, Serialize)]
 pub struct ZkIdSignature {
+    /// The version of this signature's wire format, checked against `MAX_SUPPORTED_VERSION` so
+    /// that future changes to the circuit's public inputs, the nonce-commitment construction, or
+    /// the claim set can roll out without breaking the BCS format for signatures already on the
+    /// wire. Leading so it can be inspected before the rest of the (version-dependent) fields are
+    /// interpreted.
+    pub version: u8,
+
     /// A \[ZKPoK of an\] OpenID signature over several relevant fields (e.g., `aud`, `sub`, `iss`,
     /// `nonce`) where `nonce` contains a commitment to `ephemeral_pubkey` and an expiration time
     /// `exp_timestamp_secs`.
@@ -268,16 +489,28 @@ pub struct ZkIdSignature {
 
     /// A short lived public key used to verify the `ephemeral_signature`.
     pub ephemeral_pubkey: EphemeralPublicKey,
-    /// The signature of the transaction signed by the private key of the `ephemeral_pubkey`.
-    pub ephemeral_signature: EphemeralSignature,
+    /// The signature of the transaction signed by the private key of the `ephemeral_pubkey`, or a
+    /// WebAuthn assertion when the ephemeral key lives in a platform authenticator (passkey).
+    pub ephemeral_signature: ZkIdEphemeralSignature,
+}
+
+/// Rejects a `ZkIdSignature::version` above `MAX_SUPPORTED_VERSION`, so a signature using a wire
+/// format this node doesn't understand is cleanly rejected rather than misinterpreted.
+fn ensure_supported_version(version: u8) -> Result<(), CryptoMaterialError> {
+    if version > MAX_SUPPORTED_VERSION {
+        return Err(CryptoMaterialError::DeserializationError);
+    }
+    std::result::Result::Ok(())
 }
 
 impl TryFrom<&[u8]> for ZkIdSignature {
     type Error = CryptoMaterialError;
 
     fn try_from(bytes: &[u8]) -> Result<Self, CryptoMaterialError> {
-        bcs::from_bytes::<ZkIdSignature>(bytes)
-            .map_err(|_e| CryptoMaterialError::DeserializationError)
+        let sig = bcs::from_bytes::<ZkIdSignature>(bytes)
+            .map_err(|_e| CryptoMaterialError::DeserializationError)?;
+        ensure_supported_version(sig.version)?;
+        std::result::Result::Ok(sig)
     }
 }
 
@@ -310,6 +543,59 @@ impl ZkIdSignature {
             Ok(())
         }
     }
+
+    /// Verifies the inner `[ZKPoK of an] OpenID signature` committed to by `self.sig`, against
+    /// `jwk` -- the JWK selected (by the caller, via the JWT header's `kid`) from the OIDC
+    /// provider's published key set, which may be an `RSA`, `EC`, or `OKP` key. The `Groth16Zkp`
+    /// case is checked against the (on-chain-configurable) Groth16 verifying key, so the ZK path
+    /// is cryptographically enforced rather than merely carried along unverified -- but since the
+    /// circuit itself only supports RSA-signed JWTs, `jwk` must be `JWK::RSA` on that path. The
+    /// `OpenIdSig` case remains the documented, non-ZK fallback, verified directly via
+    /// `verify_jwt_claims`/`verify_jwt_signature`, and supports whichever key type `jwk` is.
+    ///
+    /// Also verifies `ephemeral_signature` over `signing_message` -- the transaction's signing
+    /// message -- against `ephemeral_pubkey`, whether it's a raw software signature or a WebAuthn
+    /// assertion from a passkey.
+    pub fn verify_sig(
+        &self,
+        pk: &ZkIdPublicKey,
+        jwk: &JWK,
+        pvk: &PreparedVerifyingKey<ark_bn254::Bn254>,
+        current_time: &CurrentTimeMicroseconds,
+        recovery_auds: &[String],
+        signing_message: &[u8],
+    ) -> Result<()> {
+        self.ephemeral_signature
+            .verify(&self.ephemeral_pubkey, signing_message)?;
+
+        match &self.sig {
+            ZkpOrOpenIdSig::Groth16Zkp(proof) => {
+                let rsa_jwk = match jwk {
+                    JWK::RSA(rsa_jwk) => rsa_jwk,
+                    _ => bail!("The Groth16 zkID circuit only supports RSA-signed JWTs"),
+                };
+                let public_inputs_hash = compute_public_inputs_hash(
+                    self.version,
+                    pk,
+                    &self.ephemeral_pubkey,
+                    rsa_jwk,
+                    self.exp_timestamp_secs,
+                )?;
+                proof.verify_proof(public_inputs_hash, pvk)
+            },
+            ZkpOrOpenIdSig::OpenIdSig(open_id_sig) => {
+                open_id_sig.verify_jwt_claims(
+                    self.version,
+                    self.exp_timestamp_secs,
+                    &self.ephemeral_pubkey,
+                    pk,
+                    current_time,
+                    recovery_auds,
+                )?;
+                open_id_sig.verify_jwt_signature(jwk.clone(), &self.jwt_header)
+            },
+        }
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -366,6 +652,47 @@ impl IdCommitment {
     }
 }
 
+/// Which `aud` an `IdCommitment` check matched against.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IdCommitmentAudMatch {
+    /// `candidate_aud` (the `aud` the JWT presented) recomputes the committed `IdCommitment`.
+    CandidateAud,
+    /// One of the governance-approved recovery `aud`s recomputes the committed `IdCommitment`,
+    /// letting a dApp that rotated or lost its OAuth client ID still serve this user.
+    RecoveryAud(String),
+}
+
+/// Checks `candidate_aud` -- the `aud` a JWT presents, after `AudClaim::intended_aud` resolves
+/// `azp` -- against `committed_idc`, recomputing the `IdCommitment` under `candidate_aud` and,
+/// failing that, under each of `recovery_auds` in turn, so that a recovery service's governance-
+/// approved `aud` values can stand in for a dApp's original (rotated or lost) client ID without
+/// weakening the pepper-based privacy of the commitment.
+pub fn match_aud_against_idc(
+    candidate_aud: &str,
+    uid_key: &str,
+    uid_val: &str,
+    pepper: &Pepper,
+    committed_idc: &IdCommitment,
+    recovery_auds: &[String],
+) -> Result<IdCommitmentAudMatch> {
+    if IdCommitment::new_from_preimage(candidate_aud, uid_key, uid_val, pepper)?.eq(committed_idc)
+    {
+        return Ok(IdCommitmentAudMatch::CandidateAud);
+    }
+
+    for recovery_aud in recovery_auds {
+        if IdCommitment::new_from_preimage(recovery_aud, uid_key, uid_val, pepper)?
+            .eq(committed_idc)
+        {
+            return Ok(IdCommitmentAudMatch::RecoveryAud(recovery_aud.clone()));
+        }
+    }
+
+    Err(anyhow!(
+        "Address IDC verification failed: neither the JWT's 'aud' nor any recovery 'aud' matched"
+    ))
+}
+
 impl TryFrom<&[u8]> for IdCommitment {
     type Error = CryptoMaterialError;
 
@@ -433,3 +760,416 @@ fn base64url_to_str(b64: &str) -> Result<String> {
 fn seconds_from_epoch(secs: u64) -> SystemTime {
     UNIX_EPOCH + Duration::from_secs(secs)
 }
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aptos_crypto::secp256r1_ecdsa;
+    use ark_relations::r1cs::{
+        ConstraintSynthesizer, ConstraintSystemRef, LinearCombination, SynthesisError,
+    };
+    use p256::ecdsa::SigningKey;
+
+    /// A throwaway `EphemeralPublicKey`, for tests that need one to satisfy a function's
+    /// signature but don't care about its value (e.g. because the code under test rejects the
+    /// input before it ever inspects the key).
+    fn sample_epk() -> EphemeralPublicKey {
+        let signing_key = SigningKey::random(&mut rand::thread_rng());
+        EphemeralPublicKey::Secp256r1Ecdsa {
+            public_key: secp256r1_ecdsa::PublicKey::try_from(
+                signing_key.verifying_key().to_encoded_point(false).as_bytes(),
+            )
+            .unwrap(),
+        }
+    }
+
+    /// A toy `x * x = y` circuit, used only to produce a genuine Groth16 proof/verifying key pair
+    /// so `Groth16Zkp::verify_proof`'s pairing check and `parse_g1`/`parse_g2` can be exercised
+    /// against real BN254 curve points instead of hand-rolled ones.
+    #[derive(Clone)]
+    struct SquareCircuit {
+        x: ark_bn254::Fr,
+        y: ark_bn254::Fr,
+    }
+
+    impl ConstraintSynthesizer<ark_bn254::Fr> for SquareCircuit {
+        fn generate_constraints(
+            self,
+            cs: ConstraintSystemRef<ark_bn254::Fr>,
+        ) -> Result<(), SynthesisError> {
+            let x = cs.new_witness_variable(|| std::result::Result::Ok(self.x))?;
+            let y = cs.new_input_variable(|| std::result::Result::Ok(self.y))?;
+            cs.enforce_constraint(
+                LinearCombination::from(x),
+                LinearCombination::from(x),
+                LinearCombination::from(y),
+            )
+        }
+    }
+
+    fn g1_to_limbs(p: ark_bn254::G1Affine) -> G1 {
+        vec![p.x.to_string(), p.y.to_string()]
+    }
+
+    fn g2_to_limbs(p: ark_bn254::G2Affine) -> G2 {
+        vec![
+            vec![p.x.c0.to_string(), p.x.c1.to_string()],
+            vec![p.y.c0.to_string(), p.y.c1.to_string()],
+        ]
+    }
+
+    fn sample_proof() -> (Groth16Zkp, ark_bn254::Fr, PreparedVerifyingKey<ark_bn254::Bn254>) {
+        let x = ark_bn254::Fr::from(3u64);
+        let y = ark_bn254::Fr::from(9u64);
+
+        let (proving_key, verifying_key) = ark_groth16::Groth16::<ark_bn254::Bn254>::circuit_specific_setup(
+            SquareCircuit { x, y },
+            &mut rand::thread_rng(),
+        )
+        .expect("circuit setup failed");
+        let pvk = ark_groth16::prepare_verifying_key(&verifying_key);
+
+        let proof = ark_groth16::Groth16::<ark_bn254::Bn254>::prove(
+            &proving_key,
+            SquareCircuit { x, y },
+            &mut rand::thread_rng(),
+        )
+        .expect("proving failed");
+
+        let zkp = Groth16Zkp {
+            a: g1_to_limbs(proof.a),
+            b: g2_to_limbs(proof.b),
+            c: g1_to_limbs(proof.c),
+        };
+
+        (zkp, y, pvk)
+    }
+
+    #[test]
+    fn verifies_a_valid_proof() {
+        let (zkp, public_input, pvk) = sample_proof();
+
+        zkp.verify_proof(public_input, &pvk).unwrap();
+    }
+
+    #[test]
+    fn rejects_a_mismatched_public_input() {
+        let (zkp, _public_input, pvk) = sample_proof();
+
+        zkp.verify_proof(ark_bn254::Fr::from(10u64), &pvk)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn parse_g1_rejects_wrong_limb_count() {
+        parse_g1(&vec!["1".to_string()]).unwrap_err();
+    }
+
+    #[test]
+    fn parse_g2_rejects_wrong_limb_count() {
+        parse_g2(&vec![vec!["1".to_string(), "2".to_string()]]).unwrap_err();
+    }
+
+    #[test]
+    fn parse_g2_rejects_wrong_fq2_arity() {
+        parse_g2(&vec![
+            vec!["1".to_string()],
+            vec!["1".to_string(), "2".to_string()],
+        ])
+        .unwrap_err();
+    }
+
+    #[test]
+    fn parse_g1_rejects_an_off_curve_point_instead_of_panicking() {
+        // (1, 1) is not a solution to BN254's y^2 = x^3 + 3.
+        parse_g1(&vec!["1".to_string(), "1".to_string()]).unwrap_err();
+    }
+
+    #[test]
+    fn parse_g2_rejects_an_off_curve_point_instead_of_panicking() {
+        parse_g2(&vec![
+            vec!["1".to_string(), "1".to_string()],
+            vec!["1".to_string(), "1".to_string()],
+        ])
+        .unwrap_err();
+    }
+
+    #[test]
+    fn compute_public_inputs_hash_accepts_a_real_rsa_modulus() {
+        // A real RSA modulus's base64url text is ~342 chars for a 2048-bit key -- well over
+        // MAX_RSA_MODULUS_BYTES (256) -- so this only works if the modulus is base64-decoded to
+        // its raw ~256 bytes before being packed, not hashed as a plaintext string.
+        let private_key =
+            rsa::RsaPrivateKey::new(&mut rand::thread_rng(), 2048).expect("key generation failed");
+        let rsa_jwk = RSA_JWK {
+            kid: "test-kid".to_string(),
+            kty: "RSA".to_string(),
+            alg: "RS256".to_string(),
+            e: base64::encode_config(
+                rsa::PublicKeyParts::e(&private_key).to_bytes_be(),
+                base64::URL_SAFE_NO_PAD,
+            ),
+            n: base64::encode_config(
+                rsa::PublicKeyParts::n(&private_key).to_bytes_be(),
+                base64::URL_SAFE_NO_PAD,
+            ),
+        };
+        let pepper = Pepper::from_number(1);
+        let idc = IdCommitment::new_from_preimage("client-1", "sub", "user-1", &pepper).unwrap();
+        let pk = ZkIdPublicKey {
+            iss: "https://accounts.example.com".to_string(),
+            idc,
+        };
+        let epk = sample_epk();
+
+        compute_public_inputs_hash(0, &pk, &epk, &rsa_jwk, 1_700_000_000).unwrap();
+    }
+
+    #[test]
+    fn rejects_an_unsupported_signature_version() {
+        ensure_supported_version(MAX_SUPPORTED_VERSION).unwrap();
+        ensure_supported_version(MAX_SUPPORTED_VERSION + 1).unwrap_err();
+    }
+
+    #[test]
+    fn intended_aud_resolves_a_single_aud_with_no_azp() {
+        let aud = AudClaim::Single("client-1".to_string());
+        assert_eq!(aud.intended_aud(None).unwrap(), "client-1");
+    }
+
+    #[test]
+    fn intended_aud_resolves_a_single_aud_matching_azp() {
+        let aud = AudClaim::Single("client-1".to_string());
+        assert_eq!(aud.intended_aud(Some("client-1")).unwrap(), "client-1");
+    }
+
+    #[test]
+    fn intended_aud_rejects_azp_mismatch_on_a_single_aud() {
+        let aud = AudClaim::Single("client-1".to_string());
+        aud.intended_aud(Some("client-2")).unwrap_err();
+    }
+
+    #[test]
+    fn intended_aud_requires_azp_for_an_array_aud() {
+        let aud = AudClaim::Multiple(vec!["client-1".to_string(), "client-2".to_string()]);
+        aud.intended_aud(None).unwrap_err();
+    }
+
+    #[test]
+    fn intended_aud_resolves_an_array_aud_via_azp() {
+        let aud = AudClaim::Multiple(vec!["client-1".to_string(), "client-2".to_string()]);
+        assert_eq!(aud.intended_aud(Some("client-2")).unwrap(), "client-2");
+    }
+
+    #[test]
+    fn intended_aud_rejects_an_azp_not_present_in_the_array_aud() {
+        let aud = AudClaim::Multiple(vec!["client-1".to_string(), "client-2".to_string()]);
+        aud.intended_aud(Some("client-3")).unwrap_err();
+    }
+
+    /// Everything `OpenIdSig::verify_jwt_claims` needs to check a well-formed JWT, with the time
+    /// claims broken out so tests can push them past their boundaries.
+    struct ClaimsFixture {
+        aud: String,
+        uid_key: String,
+        uid_val: String,
+        version: u8,
+        iat: u64,
+        exp: u64,
+        nbf: Option<u64>,
+        exp_timestamp_secs: u64,
+    }
+
+    impl Default for ClaimsFixture {
+        fn default() -> Self {
+            let iat = 1_700_000_000;
+            ClaimsFixture {
+                aud: "client-1".to_string(),
+                uid_key: "sub".to_string(),
+                uid_val: "user-1".to_string(),
+                version: 0,
+                iat,
+                exp: iat + 3600,
+                nbf: None,
+                exp_timestamp_secs: iat + 3600,
+            }
+        }
+    }
+
+    impl ClaimsFixture {
+        const ISS: &'static str = "https://accounts.example.com";
+
+        fn build(&self) -> (OpenIdSig, ZkIdPublicKey, EphemeralPublicKey) {
+            let pepper = Pepper::from_number(1);
+            let idc =
+                IdCommitment::new_from_preimage(&self.aud, &self.uid_key, &self.uid_val, &pepper)
+                    .unwrap();
+            let pk = ZkIdPublicKey {
+                iss: Self::ISS.to_string(),
+                idc,
+            };
+            let epk = sample_epk();
+
+            let open_id_sig = OpenIdSig {
+                jwt_sig: "".to_string(),
+                jwt_payload: "".to_string(),
+                uid_key: self.uid_key.clone(),
+                epk_blinder: [0u8; EPK_BLINDER_NUM_BYTES],
+                pepper,
+            };
+            let nonce = open_id_sig
+                .reconstruct_oauth_nonce(self.version, self.exp_timestamp_secs, &epk)
+                .unwrap();
+
+            let jwt_payload = base64::encode_config(
+                serde_json::to_vec(&serde_json::json!({
+                    "iss": Self::ISS,
+                    "aud": self.aud,
+                    "sub": self.uid_val,
+                    "nonce": nonce,
+                    "iat": self.iat,
+                    "exp": self.exp,
+                    "nbf": self.nbf,
+                }))
+                .unwrap(),
+                base64::URL_SAFE,
+            );
+
+            (OpenIdSig { jwt_payload, ..open_id_sig }, pk, epk)
+        }
+    }
+
+    #[test]
+    fn verify_jwt_claims_accepts_a_well_formed_jwt() {
+        let fixture = ClaimsFixture::default();
+        let (open_id_sig, pk, epk) = fixture.build();
+        let current_time = CurrentTimeMicroseconds {
+            microseconds: (fixture.iat + 10) * 1_000_000,
+        };
+
+        open_id_sig
+            .verify_jwt_claims(
+                fixture.version,
+                fixture.exp_timestamp_secs,
+                &epk,
+                &pk,
+                &current_time,
+                &[],
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn verify_jwt_claims_rejects_an_expired_jwt() {
+        let fixture = ClaimsFixture::default();
+        let (open_id_sig, pk, epk) = fixture.build();
+        let current_time = CurrentTimeMicroseconds {
+            microseconds: (fixture.exp + 1) * 1_000_000,
+        };
+
+        open_id_sig
+            .verify_jwt_claims(
+                fixture.version,
+                fixture.exp_timestamp_secs,
+                &epk,
+                &pk,
+                &current_time,
+                &[],
+            )
+            .unwrap_err();
+    }
+
+    #[test]
+    fn verify_jwt_claims_rejects_a_not_yet_valid_jwt() {
+        let mut fixture = ClaimsFixture::default();
+        fixture.nbf = Some(fixture.iat + 1_000);
+        let (open_id_sig, pk, epk) = fixture.build();
+        let current_time = CurrentTimeMicroseconds {
+            microseconds: (fixture.iat + 10) * 1_000_000,
+        };
+
+        open_id_sig
+            .verify_jwt_claims(
+                fixture.version,
+                fixture.exp_timestamp_secs,
+                &epk,
+                &pk,
+                &current_time,
+                &[],
+            )
+            .unwrap_err();
+    }
+
+    #[test]
+    fn verify_jwt_claims_rejects_an_implausible_iat_clock_skew() {
+        let mut fixture = ClaimsFixture::default();
+        let block_time_secs = fixture.iat;
+        fixture.iat = block_time_secs + MAX_IAT_CLOCK_SKEW_SECS + 100;
+        fixture.exp = fixture.iat + 3600;
+        fixture.exp_timestamp_secs = fixture.iat + 3600;
+        let (open_id_sig, pk, epk) = fixture.build();
+        let current_time = CurrentTimeMicroseconds {
+            microseconds: block_time_secs * 1_000_000,
+        };
+
+        open_id_sig
+            .verify_jwt_claims(
+                fixture.version,
+                fixture.exp_timestamp_secs,
+                &epk,
+                &pk,
+                &current_time,
+                &[],
+            )
+            .unwrap_err();
+    }
+
+    #[test]
+    fn match_aud_against_idc_accepts_the_jwts_own_aud() {
+        let pepper = Pepper::from_number(1);
+        let idc = IdCommitment::new_from_preimage("client-1", "sub", "user-1", &pepper).unwrap();
+
+        let result = match_aud_against_idc("client-1", "sub", "user-1", &pepper, &idc, &[]).unwrap();
+
+        assert_eq!(result, IdCommitmentAudMatch::CandidateAud);
+    }
+
+    #[test]
+    fn match_aud_against_idc_accepts_a_recovery_aud() {
+        let pepper = Pepper::from_number(1);
+        // The IDC was committed under the dApp's original, now-rotated-away-from, client ID.
+        let idc = IdCommitment::new_from_preimage("old-client", "sub", "user-1", &pepper).unwrap();
+
+        let result = match_aud_against_idc(
+            "new-client",
+            "sub",
+            "user-1",
+            &pepper,
+            &idc,
+            &["unrelated-client".to_string(), "old-client".to_string()],
+        )
+        .unwrap();
+
+        assert_eq!(
+            result,
+            IdCommitmentAudMatch::RecoveryAud("old-client".to_string())
+        );
+    }
+
+    #[test]
+    fn match_aud_against_idc_rejects_when_no_aud_matches() {
+        let pepper = Pepper::from_number(1);
+        let idc = IdCommitment::new_from_preimage("client-1", "sub", "user-1", &pepper).unwrap();
+
+        match_aud_against_idc(
+            "other-client",
+            "sub",
+            "user-1",
+            &pepper,
+            &idc,
+            &["another-client".to_string()],
+        )
+        .unwrap_err();
+    }
+}